@@ -0,0 +1,317 @@
+//! Combine two extractors or responses into a single type.
+//!
+//! [`Either`] is symmetric: it is both an extractor and a response. As a response
+//! it forwards to whichever variant is present, letting a handler return
+//! `Either<Json<A>, Redirect>` without boxing to a `Response` by hand. As an
+//! extractor it buffers the request body once
+//! and offers it to each branch in turn, so body-consuming extractors such as
+//! [`Json`](axum::extract::Json) and [`Form`](axum::extract::Form) can be tried
+//! back to back:
+//!
+//! ```rust,no_run
+//! use axum_extra::either::Either;
+//! use axum::{extract::Json, Form};
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Info {
+//!     name: String,
+//! }
+//!
+//! async fn handler(payload: Either<Json<Info>, Form<Info>>) {
+//!     // `payload` is `Either::E1` for JSON and `Either::E2` for a url-encoded form.
+//! }
+//! ```
+
+use async_trait::async_trait;
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::{FromRequest, RequestParts},
+    response::{IntoResponse, Response},
+    BoxError,
+};
+use http::{Request, StatusCode};
+use http_body::{LengthLimitError, Limited};
+
+/// Default cap on the number of bytes [`Either`] will buffer when no
+/// [`EitherBodyLimit`] is present in the request extensions.
+///
+/// Matches axum's own [`DefaultBodyLimit`](axum::extract::DefaultBodyLimit) of
+/// 2 MiB so that `Either` neither accepts more nor rejects less than a plain
+/// `Json`/`Form` handler would by default.
+const DEFAULT_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Request extension limiting how many bytes [`Either`] buffers before giving up
+/// with a `413 Payload Too Large` rejection.
+///
+/// Because an extractor cannot take constructor arguments, the limit is supplied
+/// out of band through the request extensions, mirroring actix-web's
+/// `PayloadConfig`. Insert it with a layer such as
+/// [`Extension`](axum::Extension) (or `RequestExt`) ahead of the handler:
+///
+/// ```rust,no_run
+/// use axum_extra::either::EitherBodyLimit;
+/// use axum::{Extension, Router};
+///
+/// let app: Router = Router::new().layer(Extension(EitherBodyLimit::new(64 * 1024)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct EitherBodyLimit(pub usize);
+
+impl EitherBodyLimit {
+    /// Create a new limit of `max_bytes`.
+    pub fn new(max_bytes: usize) -> Self {
+        Self(max_bytes)
+    }
+}
+
+/// Combines two extractors or responses into a single type.
+///
+/// See the [module docs](self) for details.
+#[derive(Debug, Clone, Copy)]
+pub enum Either<E1, E2> {
+    #[allow(missing_docs)]
+    E1(E1),
+    #[allow(missing_docs)]
+    E2(E2),
+}
+
+impl<E1, E2> IntoResponse for Either<E1, E2>
+where
+    E1: IntoResponse,
+    E2: IntoResponse,
+{
+    fn into_response(self) -> Response {
+        match self {
+            Either::E1(inner) => inner.into_response(),
+            Either::E2(inner) => inner.into_response(),
+        }
+    }
+}
+
+#[async_trait]
+impl<S, B, E1, E2> FromRequest<S, B> for Either<E1, E2>
+where
+    E1: FromRequest<S, Body> + Send,
+    E1::Rejection: Send,
+    E2: FromRequest<S, Body> + Send,
+    E2::Rejection: Send,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+    S: Clone + Send + Sync + 'static,
+{
+    type Rejection = EitherRejection<E1::Rejection, E2::Rejection>;
+
+    async fn from_request(req: &mut RequestParts<S, B>) -> Result<Self, Self::Rejection> {
+        let limit = req
+            .extensions()
+            .get::<EitherBodyLimit>()
+            .map(|limit| limit.0)
+            .unwrap_or(DEFAULT_BODY_LIMIT);
+
+        // Reject up front when the declared length already exceeds the limit so we
+        // never start reading an oversized body into memory.
+        if let Some(content_length) = req
+            .headers()
+            .get(http::header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<usize>().ok())
+        {
+            if content_length > limit {
+                return Err(EitherRejection::PayloadTooLarge);
+            }
+        }
+
+        // Drain the body once, aborting the read as soon as it crosses `limit` so a
+        // chunked request that omits (or under-reports) its length can't stream an
+        // unbounded body into memory. `Bytes` is reference counted, so both attempts
+        // below start from identical, cheaply cloned input. A body that has already
+        // been taken (e.g. by an outer combinator whose branch is itself an `Either`)
+        // is treated as empty rather than an error, so header-only branches still run.
+        let bytes = match req.take_body() {
+            Some(body) => match hyper::body::to_bytes(Limited::new(body, limit)).await {
+                Ok(bytes) => bytes,
+                Err(err) if err.is::<LengthLimitError>() => {
+                    return Err(EitherRejection::PayloadTooLarge)
+                }
+                Err(_) => {
+                    return Err(EitherRejection::BufferBody(
+                        StatusCode::BAD_REQUEST.into_response(),
+                    ))
+                }
+            },
+            None => Bytes::new(),
+        };
+
+        let mut left = rebuild(req, bytes.clone());
+        match E1::from_request(&mut left).await {
+            Ok(value) => return Ok(Either::E1(value)),
+            Err(e1) => {
+                let mut right = rebuild(req, bytes);
+                match E2::from_request(&mut right).await {
+                    Ok(value) => Ok(Either::E2(value)),
+                    Err(e2) => Err(EitherRejection::Both { e1, e2 }),
+                }
+            }
+        }
+    }
+}
+
+/// Build a fresh [`RequestParts`] backed by the buffered `bytes` while preserving
+/// the original method, uri, version, headers and extensions so that header-only
+/// extractors keep seeing the original request.
+fn rebuild<S, B>(req: &RequestParts<S, B>, bytes: Bytes) -> RequestParts<S, Body>
+where
+    S: Clone,
+{
+    let mut builder = Request::builder()
+        .method(req.method().clone())
+        .uri(req.uri().clone())
+        .version(req.version());
+
+    if let Some(headers) = builder.headers_mut() {
+        *headers = req.headers().clone();
+    }
+
+    let mut request = builder
+        .body(Body::from(bytes))
+        .expect("method, uri and headers came from a valid request");
+    *request.extensions_mut() = req.extensions().clone();
+
+    RequestParts::with_state(req.state().clone(), request)
+}
+
+/// Rejection used by [`Either`] when neither branch could be extracted.
+#[derive(Debug)]
+pub enum EitherRejection<E1, E2> {
+    /// Buffering the request body failed.
+    BufferBody(Response),
+    /// The buffered body exceeded the configured [`EitherBodyLimit`].
+    PayloadTooLarge,
+    /// Both branches were tried and both rejected.
+    ///
+    /// Both rejections are carried for inspection. [`IntoResponse`] surfaces the
+    /// branch whose rejection is most specific — a `415 Unsupported Media Type`
+    /// means that branch never matched the request's content type, so the other
+    /// branch's rejection is preferred; the unused branch is dropped from the
+    /// response.
+    Both {
+        /// The rejection returned by the first branch.
+        e1: E1,
+        /// The rejection returned by the second branch.
+        e2: E2,
+    },
+}
+
+impl<E1, E2> IntoResponse for EitherRejection<E1, E2>
+where
+    E1: IntoResponse,
+    E2: IntoResponse,
+{
+    fn into_response(self) -> Response {
+        match self {
+            EitherRejection::BufferBody(res) => res,
+            EitherRejection::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE.into_response(),
+            EitherRejection::Both { e1, e2 } => {
+                let e1 = e1.into_response();
+                let e2 = e2.into_response();
+                // A `415 Unsupported Media Type` means that branch never matched the
+                // content type, so it tells the caller nothing useful. Prefer the
+                // other branch's rejection; otherwise fall back to the first branch.
+                match (e1.status(), e2.status()) {
+                    (StatusCode::UNSUPPORTED_MEDIA_TYPE, _) => e2,
+                    (_, StatusCode::UNSUPPORTED_MEDIA_TYPE) => e1,
+                    _ => e1,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::*;
+    use axum::{routing::post, Extension, Form, Json, Router};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Info {
+        name: String,
+    }
+
+    async fn echo(payload: Either<Json<Info>, Form<Info>>) -> String {
+        match payload {
+            Either::E1(Json(info)) => format!("json: {}", info.name),
+            Either::E2(Form(info)) => format!("form: {}", info.name),
+        }
+    }
+
+    #[tokio::test]
+    async fn json_or_form() {
+        let app = Router::new().route("/", post(echo));
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .json(&serde_json::json!({ "name": "foo" }))
+            .send()
+            .await;
+        assert_eq!(res.text().await, "json: foo");
+
+        // The left (JSON) extractor consumes the body, but the buffered bytes let
+        // the right (form) extractor still succeed on a url-encoded request.
+        let res = client.post("/").form(&[("name", "bar")]).send().await;
+        assert_eq!(res.text().await, "form: bar");
+    }
+
+    #[tokio::test]
+    async fn body_limit_overrides_default() {
+        // An 8 byte limit supplied via extensions overrides `DEFAULT_BODY_LIMIT`, so
+        // a larger payload is rejected with `413 Payload Too Large`.
+        let app = Router::new()
+            .route("/", post(echo))
+            .layer(Extension(EitherBodyLimit::new(8)));
+        let client = TestClient::new(app);
+
+        let res = client
+            .post("/")
+            .json(&serde_json::json!({ "name": "much longer than eight bytes" }))
+            .send()
+            .await;
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // A payload within the limit still extracts normally.
+        let res = client.post("/").form(&[("name", "ok")]).send().await;
+        assert_eq!(res.text().await, "form: ok");
+    }
+
+    #[tokio::test]
+    async fn into_response_forwards_to_present_variant() {
+        let res = Either::<&str, StatusCode>::E1("left").into_response();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = Either::<&str, StatusCode>::E2(StatusCode::IM_A_TEAPOT).into_response();
+        assert_eq!(res.status(), StatusCode::IM_A_TEAPOT);
+    }
+
+    #[tokio::test]
+    async fn both_branches_reject() {
+        let app = Router::new().route("/", post(echo));
+        let client = TestClient::new(app);
+
+        // Invalid JSON with a JSON content type: neither branch can extract it, so
+        // the combined rejection is returned rather than an opaque success. The
+        // surfaced rejection must come from the JSON branch (which matched the
+        // content type), not the form branch's `415 Unsupported Media Type`.
+        let res = client
+            .post("/")
+            .header("content-type", "application/json")
+            .body("not json")
+            .send()
+            .await;
+        assert!(res.status().is_client_error());
+        assert_ne!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+}
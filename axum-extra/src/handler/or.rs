@@ -3,11 +3,10 @@ use crate::either::Either;
 use axum::{
     extract::{FromRequest, RequestParts},
     handler::Handler,
-    http::Request,
+    http::{Request, StatusCode},
     response::{IntoResponse, Response},
 };
 use futures_util::future::{BoxFuture, Either as EitherFuture, FutureExt, Map};
-use http::StatusCode;
 use std::{future::Future, marker::PhantomData, sync::Arc};
 
 /// [`Handler`] that runs one [`Handler`] and if that rejects it'll fallback to another
@@ -73,15 +72,29 @@ where
         Box::pin(async move {
             let mut req = RequestParts::with_state_arc(Arc::clone(&state), req);
 
-            if let Ok(lt) = req.extract::<Lt>().await {
-                return self.lhs.call(state, lt).await;
+            let lhs_rejection = match req.extract::<Lt>().await {
+                Ok(lt) => return self.lhs.call(state, lt).await,
+                Err(rejection) => rejection.into_response(),
+            };
+
+            match req.extract::<Rt>().await {
+                Ok(rt) => self.rhs.call(state, rt).await,
+                // Every branch rejected. Rather than hiding the cause behind an
+                // opaque 404 we surface one of the real rejections. A `415
+                // Unsupported Media Type` means that branch never matched the
+                // request's content type, so it tells the caller nothing useful;
+                // we prefer the other branch's rejection and otherwise fall back
+                // to the right-hand one. `EitherRejection::Both` makes the same
+                // 415 check but falls back to its first branch instead.
+                Err(rejection) => {
+                    let rhs_rejection = rejection.into_response();
+                    match (lhs_rejection.status(), rhs_rejection.status()) {
+                        (_, StatusCode::UNSUPPORTED_MEDIA_TYPE) => lhs_rejection,
+                        (StatusCode::UNSUPPORTED_MEDIA_TYPE, _) => rhs_rejection,
+                        _ => rhs_rejection,
+                    }
+                }
             }
-
-            if let Ok(rt) = req.extract::<Rt>().await {
-                return self.rhs.call(state, rt).await;
-            }
-
-            StatusCode::NOT_FOUND.into_response()
         })
     }
 }